@@ -0,0 +1,101 @@
+use axum::{http::Request, middleware::Next, response::IntoResponse, Extension, Json};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::Component;
+
+use crate::config::Config;
+use crate::error::{ApiError, ErrorBody};
+
+#[derive(Debug, Serialize, Deserialize, Component)]
+pub struct LoginInput {
+    username: String,
+    password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Component)]
+pub struct LoginResponse {
+    token: String,
+    expires_in: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TokenClaims {
+    pub sub: String,
+    pub iat: usize,
+    pub exp: usize,
+}
+
+#[utoipa::path(
+    post,
+    path = "/login",
+    responses(
+        (status = 200, description = "Login succesful, returns a bearer token", body = LoginResponse),
+        (status = 401, description = "Invalid credentials", body = ErrorBody)
+    ),
+    request_body = LoginInput,
+)]
+pub async fn login(
+    Json(input): Json<LoginInput>,
+    Extension(config): Extension<Arc<Config>>,
+) -> Result<Json<LoginResponse>, ApiError> {
+    if input.username != config.admin_username || input.password != config.admin_password {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let now = Utc::now();
+    let iat = now.timestamp() as usize;
+    let exp = (now + Duration::minutes(config.jwt_maxage)).timestamp() as usize;
+    let claims = TokenClaims {
+        sub: input.username,
+        iat,
+        exp,
+    };
+
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(config.jwt_secret.as_bytes()),
+    )
+    .map_err(|_| ApiError::Internal)?;
+
+    Ok(Json(LoginResponse {
+        token,
+        expires_in: config.jwt_expires_in.clone(),
+    }))
+}
+
+/// Route guard in the style of `track_metrics`: validates the `Authorization`
+/// bearer token and injects the decoded claims as a request extension so
+/// downstream handlers can read `Extension<TokenClaims>`.
+pub async fn require_auth<B>(mut req: Request<B>, next: Next<B>) -> impl IntoResponse {
+    let config = req
+        .extensions()
+        .get::<Arc<Config>>()
+        .expect("Config extension missing")
+        .clone();
+
+    let token = req
+        .headers()
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    let token = match token {
+        Some(token) => token,
+        None => return Err(ApiError::Unauthorized),
+    };
+
+    let claims = decode::<TokenClaims>(
+        token,
+        &DecodingKey::from_secret(config.jwt_secret.as_bytes()),
+        &Validation::default(),
+    )
+    .map_err(|_| ApiError::Unauthorized)?
+    .claims;
+
+    req.extensions_mut().insert(claims);
+
+    Ok(next.run(req).await)
+}
@@ -0,0 +1,45 @@
+use std::net::SocketAddr;
+
+/// Runtime configuration sourced from the environment. Fails fast at
+/// startup rather than letting handlers discover a missing secret later.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub jwt_secret: String,
+    /// Human-readable token lifetime (e.g. `"60m"`), advertised to clients in
+    /// `/login`'s response so they know when to re-authenticate. `jwt_maxage`
+    /// remains the source of truth for the actual `exp` claim.
+    pub jwt_expires_in: String,
+    pub jwt_maxage: i64,
+    pub bind_addr: SocketAddr,
+    pub admin_username: String,
+    pub admin_password: String,
+}
+
+impl Config {
+    pub fn init() -> Config {
+        let jwt_secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+        let jwt_expires_in =
+            std::env::var("JWT_EXPIRES_IN").expect("JWT_EXPIRES_IN must be set");
+        let jwt_maxage = std::env::var("JWT_MAXAGE")
+            .expect("JWT_MAXAGE must be set")
+            .parse::<i64>()
+            .expect("JWT_MAXAGE must be an integer number of minutes");
+        let bind_addr = std::env::var("BIND_ADDR")
+            .unwrap_or_else(|_| "127.0.0.1:3000".to_owned())
+            .parse::<SocketAddr>()
+            .expect("BIND_ADDR must be a valid socket address");
+        let admin_username =
+            std::env::var("ADMIN_USERNAME").expect("ADMIN_USERNAME must be set");
+        let admin_password =
+            std::env::var("ADMIN_PASSWORD").expect("ADMIN_PASSWORD must be set");
+
+        Config {
+            jwt_secret,
+            jwt_expires_in,
+            jwt_maxage,
+            bind_addr,
+            admin_username,
+            admin_password,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use thiserror::Error;
+use utoipa::Component;
+
+/// Unified error shape for handlers, so clients get a consistent
+/// machine-readable `{code, message}` body instead of an empty response.
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("{0} was not found")]
+    NotFound(String),
+    #[error("{0}")]
+    Validation(String),
+    #[error("missing or invalid credentials")]
+    Unauthorized,
+    #[error("internal server error")]
+    Internal,
+}
+
+#[derive(Debug, Serialize, Component)]
+pub struct ErrorBody {
+    code: u16,
+    message: String,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Validation(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
+            ApiError::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        let body = ErrorBody {
+            code: status.as_u16(),
+            message: self.to_string(),
+        };
+
+        (status, Json(body)).into_response()
+    }
+}
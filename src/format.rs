@@ -0,0 +1,96 @@
+use axum::{
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ApiError;
+use crate::Todo;
+
+/// Output representation for todo responses, picked per request from the
+/// `Accept` header or an `?format=` override.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseFormat {
+    Json,
+    Pretty,
+    Csv,
+}
+
+impl ResponseFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResponseFormat::Json => "json",
+            ResponseFormat::Pretty => "pretty",
+            ResponseFormat::Csv => "csv",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FormatQuery {
+    format: Option<String>,
+}
+
+pub fn negotiate(headers: &HeaderMap, format: &FormatQuery) -> ResponseFormat {
+    match format.format.as_deref() {
+        Some("csv") => return ResponseFormat::Csv,
+        Some("pretty") => return ResponseFormat::Pretty,
+        Some("json") => return ResponseFormat::Json,
+        _ => {}
+    }
+
+    let accept = headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("");
+
+    if accept.contains("text/csv") {
+        ResponseFormat::Csv
+    } else if accept.contains("pretty") {
+        ResponseFormat::Pretty
+    } else {
+        ResponseFormat::Json
+    }
+}
+
+pub fn render_todos(format: ResponseFormat, todos: &[Todo]) -> Response {
+    let mut response = match format {
+        ResponseFormat::Json => Json(todos).into_response(),
+        ResponseFormat::Pretty => pretty_json(todos),
+        ResponseFormat::Csv => csv(todos),
+    };
+    response.extensions_mut().insert(format);
+    response
+}
+
+pub fn render_todo(format: ResponseFormat, todo: &Todo) -> Response {
+    let mut response = match format {
+        ResponseFormat::Json => Json(todo).into_response(),
+        ResponseFormat::Pretty => pretty_json(todo),
+        ResponseFormat::Csv => csv(std::slice::from_ref(todo)),
+    };
+    response.extensions_mut().insert(format);
+    response
+}
+
+fn pretty_json<T: Serialize>(value: &T) -> Response {
+    match serde_json::to_string_pretty(value) {
+        Ok(body) => ([(header::CONTENT_TYPE, "application/json")], body).into_response(),
+        Err(_) => ApiError::Internal.into_response(),
+    }
+}
+
+fn csv(todos: &[Todo]) -> Response {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    for todo in todos {
+        if writer.serialize(todo).is_err() {
+            return ApiError::Internal.into_response();
+        }
+    }
+
+    match writer.into_inner().ok().and_then(|bytes| String::from_utf8(bytes).ok()) {
+        Some(body) => ([(header::CONTENT_TYPE, "text/csv")], body).into_response(),
+        None => ApiError::Internal.into_response(),
+    }
+}
@@ -2,19 +2,20 @@ use axum::error_handling::HandleErrorLayer;
 use axum::BoxError;
 use axum::{
     extract::Path,
+    extract::Query,
     response::IntoResponse,
     routing::get,
     Extension, Json, Router,
     extract::MatchedPath,
-    http::Request,
+    http::{HeaderMap, Request},
     middleware::{self, Next},
+    response::Response,
 };
 use hyper::StatusCode;
 use serde::{Deserialize, Serialize};
-use std::net::SocketAddr;
 use std::{
     collections::HashMap,
-    sync::{Arc, RwLock},
+    sync::Arc,
     time::{Duration, Instant},
     future::ready,
 };
@@ -23,21 +24,81 @@ use tower_http::trace::TraceLayer;
 use uuid::Uuid;
 use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
 
-use utoipa::{OpenApi, Component};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Component, Modify, OpenApi};
+use utoipa_swagger_ui::SwaggerUi;
+
+mod auth;
+mod config;
+mod error;
+mod format;
+mod repository;
+mod sql_repository;
+
+use auth::{login, require_auth, LoginInput, LoginResponse};
+use config::Config;
+use error::{ApiError, ErrorBody};
+use format::{negotiate, render_todo, render_todos, FormatQuery, ResponseFormat};
+use repository::{InMemoryRepository, TodoRepository};
+use sql_repository::SqlRepository;
+
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "bearer_auth",
+                SecurityScheme::Http(
+                    HttpBuilder::new()
+                        .scheme(HttpAuthScheme::Bearer)
+                        .bearer_format("JWT")
+                        .build(),
+                ),
+            )
+        }
+    }
+}
 
 #[derive(OpenApi)]
-#[openapi(handlers(get_todo_by_id, save_todo, get_todos), components(Todo, CreateTodo))]
+#[openapi(
+    handlers(
+        get_todo_by_id,
+        save_todo,
+        get_todos,
+        update_todo,
+        toggle_todo,
+        delete_todo,
+        search_todos,
+        login
+    ),
+    components(Todo, CreateTodo, UpdateTodo, LoginInput, LoginResponse, ErrorBody),
+    modifiers(&SecurityAddon)
+)]
 struct ApiDoc;
 
-type Db = Arc<RwLock<HashMap<Uuid, Todo>>>;
+type Db = Arc<dyn TodoRepository>;
 
-fn app(db: Db) -> Router {
+fn app(db: Db, config: Arc<Config>) -> Router {
     let recorder_handle = setup_metrics_recorder();
-    Router::new()
+
+    let protected = Router::new()
         .route("/todos", get(get_todos).post(save_todo))
-        .route("/todos/:id", get(get_todo_by_id))
+        .route(
+            "/todos/:id",
+            get(get_todo_by_id).put(update_todo).delete(delete_todo),
+        )
+        .route("/todos/:id/done", axum::routing::patch(toggle_todo))
+        .route("/todos/search", get(search_todos))
+        .route_layer(middleware::from_fn(require_auth::<axum::body::Body>));
+
+    let public = Router::new()
+        .route("/login", axum::routing::post(login))
         .route("/metrics", get(move || ready(recorder_handle.render())))
-        .route("/api-doc/openapi.json", get(openapi))
+        .merge(SwaggerUi::new("/swagger-ui").url("/api-doc/openapi.json", ApiDoc::openapi()));
+
+    protected
+        .merge(public)
         .layer(
             ServiceBuilder::new()
                 .layer(HandleErrorLayer::new(|error: BoxError| async move {
@@ -53,17 +114,33 @@ fn app(db: Db) -> Router {
                 .timeout(Duration::from_secs(10))
                 .layer(TraceLayer::new_for_http())
                 .layer(Extension(db))
+                .layer(Extension(config))
                 .into_inner(),
         )
         .route_layer(middleware::from_fn(track_metrics))
 }
 
+/// Picks a `SqlRepository` backed by `DATABASE_URL` when set, falling back
+/// to the in-memory map otherwise (e.g. for local development and tests).
+async fn build_repository() -> Result<Db, anyhow::Error> {
+    match std::env::var("DATABASE_URL") {
+        Ok(database_url) => {
+            let pool = sqlx::postgres::PgPoolOptions::new()
+                .connect(&database_url)
+                .await?;
+            sqlx::migrate!().run(&pool).await?;
+            Ok(Arc::new(SqlRepository::new(pool)))
+        }
+        Err(_) => Ok(Arc::new(InMemoryRepository::default())),
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
-    let app = app(Db::default());
-
-    // Address that server will bind to.
-    let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
+    let config = Arc::new(Config::init());
+    let addr = config.bind_addr;
+    let repository: Db = build_repository().await?;
+    let app = app(repository, config);
 
     // Use `hyper::server::Server` which is re-exported through `axum::Server` to serve the app.
     axum::Server::bind(&addr)
@@ -74,81 +151,209 @@ async fn main() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
-#[derive(Debug, Serialize, Clone, Deserialize, Component)]
+#[derive(Debug, Serialize, Clone, Deserialize, Component, sqlx::FromRow)]
 pub struct Todo {
-    id: Uuid,
-    user: Option<String>,
-    text: String,
-    completed: bool,
+    pub(crate) id: Uuid,
+    pub(crate) user: Option<String>,
+    pub(crate) text: String,
+    pub(crate) completed: bool,
 }
 
 #[derive(Debug, Serialize, Clone, Deserialize, Component)]
 pub struct CreateTodo {
-    text: String,
-    user: Option<String>,
+    pub(crate) text: String,
+    pub(crate) user: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Deserialize, Component)]
+pub struct UpdateTodo {
+    pub(crate) text: String,
+    pub(crate) user: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchParams {
+    pub(crate) text: Option<String>,
+    pub(crate) completed: Option<bool>,
+    pub(crate) user: Option<String>,
 }
 
 #[utoipa::path(
     get,
     path = "/todos",
     responses(
-        (status = 200, description = "List of todos", body = [Todo])
-    )
+        (status = 200, description = "List of todos, as JSON, pretty JSON, or CSV depending on Accept/?format=", body = [Todo])
+    ),
+    params(
+        ("format" = Option<String>, query, description = "Override content negotiation: json, pretty, or csv"),
+    ),
+    security(("bearer_auth" = []))
 )]
-pub async fn get_todos(todos: Extension<Db>) -> impl IntoResponse {
-    Json(todos.read().unwrap().values().cloned().collect::<Vec<_>>())
+pub async fn get_todos(
+    Extension(todos): Extension<Db>,
+    headers: HeaderMap,
+    Query(format_query): Query<FormatQuery>,
+) -> Response {
+    let format = negotiate(&headers, &format_query);
+    match todos.all().await {
+        Ok(todos) => render_todos(format, &todos),
+        Err(error) => error.into_response(),
+    }
 }
 
 #[utoipa::path(
     get,
     path = "/todos/{id}",
     responses(
-        (status = 200, description = "Todo found succesfully", body = Todo),
-        (status = 404, description = "Todo was not found")
+        (status = 200, description = "Todo found succesfully, as JSON, pretty JSON, or CSV depending on Accept/?format=", body = Todo),
+        (status = 404, description = "Todo was not found", body = ErrorBody)
     ),
     params(
         ("id" = Uuid, path, description = "Todo id to get Todo"),
-    )
+        ("format" = Option<String>, query, description = "Override content negotiation: json, pretty, or csv"),
+    ),
+    security(("bearer_auth" = []))
 )]
-pub async fn get_todo_by_id(Path(id): Path<Uuid>, todos: Extension<Db>) -> impl IntoResponse {
-    Json(
-        todos
-            .read()
-            .unwrap()
-            .get(&id)
-            .cloned()
-    )
+pub async fn get_todo_by_id(
+    Path(id): Path<Uuid>,
+    Extension(todos): Extension<Db>,
+    headers: HeaderMap,
+    Query(format_query): Query<FormatQuery>,
+) -> Response {
+    let format = negotiate(&headers, &format_query);
+    match todos.find(id).await {
+        Ok(Some(todo)) => render_todo(format, &todo),
+        Ok(None) => ApiError::NotFound(format!("todo {id}")).into_response(),
+        Err(error) => error.into_response(),
+    }
 }
 
 #[utoipa::path(
     post,
     path = "/todos",
     responses(
-        (status = 201, description = "Todo saved succesfully", body = Todo)
+        (status = 201, description = "Todo saved succesfully", body = Todo),
+        (status = 400, description = "Todo text must not be empty", body = ErrorBody)
     ),
     request_body = CreateTodo,
+    security(("bearer_auth" = []))
 )]
-pub async fn save_todo(Json(input): Json<CreateTodo>, todos: Extension<Db>) -> impl IntoResponse {
-    let todo = Todo {
-        id: Uuid::new_v4(),
-        user: input.user,
-        text: input.text,
-        completed: false,
-    };
+pub async fn save_todo(
+    Json(input): Json<CreateTodo>,
+    Extension(todos): Extension<Db>,
+) -> Result<(StatusCode, Json<Todo>), ApiError> {
+    if input.text.trim().is_empty() {
+        return Err(ApiError::Validation("todo text must not be empty".to_owned()));
+    }
+
+    let todo = todos.create(input).await?;
 
-    todos.write().unwrap().insert(todo.id, todo.clone());
+    Ok((StatusCode::CREATED, Json(todo)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/todos/{id}",
+    responses(
+        (status = 200, description = "Todo updated succesfully", body = Todo),
+        (status = 400, description = "Todo text must not be empty", body = ErrorBody),
+        (status = 404, description = "Todo was not found", body = ErrorBody)
+    ),
+    params(
+        ("id" = Uuid, path, description = "Todo id to update"),
+    ),
+    request_body = UpdateTodo,
+    security(("bearer_auth" = []))
+)]
+pub async fn update_todo(
+    Path(id): Path<Uuid>,
+    Json(input): Json<UpdateTodo>,
+    Extension(todos): Extension<Db>,
+) -> Result<Json<Todo>, ApiError> {
+    if input.text.trim().is_empty() {
+        return Err(ApiError::Validation("todo text must not be empty".to_owned()));
+    }
+
+    todos
+        .update(id, input)
+        .await?
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("todo {id}")))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/todos/{id}/done",
+    responses(
+        (status = 200, description = "Todo completion toggled succesfully", body = Todo),
+        (status = 404, description = "Todo was not found", body = ErrorBody)
+    ),
+    params(
+        ("id" = Uuid, path, description = "Todo id to toggle"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn toggle_todo(
+    Path(id): Path<Uuid>,
+    Extension(todos): Extension<Db>,
+) -> Result<Json<Todo>, ApiError> {
+    todos
+        .toggle(id)
+        .await?
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("todo {id}")))
+}
 
-    (StatusCode::CREATED, Json(todo))
+#[utoipa::path(
+    delete,
+    path = "/todos/{id}",
+    responses(
+        (status = 204, description = "Todo deleted succesfully"),
+        (status = 404, description = "Todo was not found", body = ErrorBody)
+    ),
+    params(
+        ("id" = Uuid, path, description = "Todo id to delete"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_todo(
+    Path(id): Path<Uuid>,
+    Extension(todos): Extension<Db>,
+) -> Result<StatusCode, ApiError> {
+    if todos.delete(id).await? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ApiError::NotFound(format!("todo {id}")))
+    }
 }
 
-async fn openapi() -> impl IntoResponse {
-    Json(ApiDoc::openapi())
+#[utoipa::path(
+    get,
+    path = "/todos/search",
+    responses(
+        (status = 200, description = "List of todos matching the search filters", body = [Todo])
+    ),
+    params(
+        ("text" = Option<String>, query, description = "Substring to match against the todo text"),
+        ("completed" = Option<bool>, query, description = "Filter by completion status"),
+        ("user" = Option<String>, query, description = "Filter by owning user"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn search_todos(
+    Query(params): Query<SearchParams>,
+    Extension(todos): Extension<Db>,
+) -> Result<Json<Vec<Todo>>, ApiError> {
+    Ok(Json(todos.search(params).await?))
 }
 
 fn setup_metrics_recorder() -> PrometheusHandle {
     const EXPONENTIAL_SECONDS: &[f64] = &[
         0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
     ];
+    const EXPONENTIAL_BYTES: &[f64] = &[
+        64.0, 256.0, 1024.0, 4096.0, 16384.0, 65536.0, 262144.0, 1048576.0,
+    ];
 
     PrometheusBuilder::new()
         .set_buckets_for_metric(
@@ -156,10 +361,50 @@ fn setup_metrics_recorder() -> PrometheusHandle {
             EXPONENTIAL_SECONDS,
         )
         .unwrap()
+        .set_buckets_for_metric(
+            Matcher::Full("http_request_size_bytes".to_string()),
+            EXPONENTIAL_BYTES,
+        )
+        .unwrap()
+        .set_buckets_for_metric(
+            Matcher::Full("http_response_size_bytes".to_string()),
+            EXPONENTIAL_BYTES,
+        )
+        .unwrap()
         .install_recorder()
         .unwrap()
 }
 
+fn content_length(headers: &axum::http::HeaderMap) -> Option<f64> {
+    headers
+        .get(axum::http::header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<f64>().ok())
+}
+
+/// Size of a response body in bytes, read from the body's `SizeHint` rather
+/// than the `Content-Length` header: axum's `Json`/`String` responses never
+/// set that header themselves, it's only added by hyper's H1 codec once the
+/// response is written to the wire, long after this middleware has run.
+fn response_size(response: &Response) -> Option<f64> {
+    axum::body::HttpBody::size_hint(response.body())
+        .exact()
+        .map(|bytes| bytes as f64)
+}
+
+/// Decrements `http_requests_in_flight` when dropped, so a handler that
+/// panics mid-request still releases the gauge instead of leaking it
+/// upward forever.
+struct InFlightGuard {
+    labels: [(&'static str, String); 2],
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        metrics::decrement_gauge!("http_requests_in_flight", 1.0, &self.labels);
+    }
+}
+
 async fn track_metrics<B>(req: Request<B>, next: Next<B>) -> impl IntoResponse {
     let start = Instant::now();
     let path = if let Some(matched_path) = req.extensions().get::<MatchedPath>() {
@@ -168,21 +413,45 @@ async fn track_metrics<B>(req: Request<B>, next: Next<B>) -> impl IntoResponse {
         req.uri().path().to_owned()
     };
     let method = req.method().clone();
+    let request_size = content_length(req.headers());
+    let in_flight_labels = [("method", method.to_string()), ("path", path.clone())];
 
+    metrics::increment_gauge!("http_requests_in_flight", 1.0, &in_flight_labels);
+    let in_flight_guard = InFlightGuard {
+        labels: in_flight_labels,
+    };
     let response = next.run(req).await;
+    drop(in_flight_guard);
 
     let latency = start.elapsed().as_secs_f64();
-    let status = response.status().as_u16().to_string();
+    let status_code = response.status().as_u16();
+    let status = status_code.to_string();
+    let format = response
+        .extensions()
+        .get::<ResponseFormat>()
+        .map_or("json", ResponseFormat::label)
+        .to_owned();
 
     let labels = [
         ("method", method.to_string()),
         ("path", path),
         ("status", status),
+        ("format", format),
     ];
 
     metrics::increment_counter!("http_requests_total", &labels);
     metrics::histogram!("http_requests_duration_seconds", latency, &labels);
 
+    if let Some(request_size) = request_size {
+        metrics::histogram!("http_request_size_bytes", request_size, &labels);
+    }
+    if let Some(response_size) = response_size(&response) {
+        metrics::histogram!("http_response_size_bytes", response_size, &labels);
+    }
+    if status_code >= 500 {
+        metrics::increment_counter!("http_requests_errors_total", &labels);
+    }
+
     response
 }
 
@@ -196,6 +465,33 @@ mod tests {
     use serde_json::{json, Value};
     use tower::ServiceExt;
 
+    fn test_config() -> Arc<Config> {
+        Arc::new(Config {
+            jwt_secret: "test-secret".to_owned(),
+            jwt_expires_in: "60m".to_owned(),
+            jwt_maxage: 60,
+            bind_addr: "127.0.0.1:0".parse().unwrap(),
+            admin_username: "admin".to_owned(),
+            admin_password: "admin-password".to_owned(),
+        })
+    }
+
+    fn bearer_token(config: &Config) -> String {
+        let now = chrono::Utc::now();
+        let claims = auth::TokenClaims {
+            sub: "test-user".to_owned(),
+            iat: now.timestamp() as usize,
+            exp: (now + chrono::Duration::minutes(config.jwt_maxage)).timestamp() as usize,
+        };
+
+        jsonwebtoken::encode(
+            &jsonwebtoken::Header::default(),
+            &claims,
+            &jsonwebtoken::EncodingKey::from_secret(config.jwt_secret.as_bytes()),
+        )
+        .unwrap()
+    }
+
     #[tokio::test]
     async fn save_note() {
         let todo1 = Todo {
@@ -210,10 +506,12 @@ mod tests {
             text: "todo 2".to_owned(),
             completed: true,
         };
-        let app = app(Arc::new(RwLock::new(HashMap::from([
+        let config = test_config();
+        let repository: Db = Arc::new(InMemoryRepository::new(HashMap::from([
             (todo1.id, todo1),
             (todo2.id, todo2),
-        ]))));
+        ])));
+        let app = app(repository, config.clone());
 
         let response = app
             .oneshot(
@@ -221,6 +519,10 @@ mod tests {
                     .method(http::Method::POST)
                     .uri("/todos")
                     .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .header(
+                        http::header::AUTHORIZATION,
+                        format!("Bearer {}", bearer_token(&config)),
+                    )
                     .body(Body::from(
                         serde_json::to_vec(&json!({"text": "test todo", "user": Some("user")}))
                             .unwrap(),
@@ -253,10 +555,12 @@ mod tests {
             completed: true,
         };
         let search_todo = todo1.clone();
-        let app = app(Arc::new(RwLock::new(HashMap::from([
+        let config = test_config();
+        let repository: Db = Arc::new(InMemoryRepository::new(HashMap::from([
             (todo1.id, todo1),
             (todo2.id, todo2),
-        ]))));
+        ])));
+        let app = app(repository, config.clone());
 
         let response = app
             .oneshot(
@@ -264,6 +568,10 @@ mod tests {
                     .method(http::Method::GET)
                     .uri(format!("/todos/{}", search_todo.id))
                     .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .header(
+                        http::header::AUTHORIZATION,
+                        format!("Bearer {}", bearer_token(&config)),
+                    )
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -279,15 +587,314 @@ mod tests {
         assert_eq!(body.id, search_todo.id);
     }
 
+    #[tokio::test]
+    async fn save_note_empty_text_returns_validation_error_body() {
+        let config = test_config();
+        let app = app(Arc::new(InMemoryRepository::default()), config.clone());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/todos")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .header(
+                        http::header::AUTHORIZATION,
+                        format!("Bearer {}", bearer_token(&config)),
+                    )
+                    .body(Body::from(
+                        serde_json::to_vec(&json!({"text": "   ", "user": Some("user")})).unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], json!(400));
+        assert_eq!(body["message"], json!("todo text must not be empty"));
+    }
+
+    #[tokio::test]
+    async fn get_note_by_id_missing_id_returns_not_found_error_body() {
+        let config = test_config();
+        let app = app(Arc::new(InMemoryRepository::default()), config.clone());
+        let missing_id = Uuid::new_v4();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri(format!("/todos/{missing_id}"))
+                    .header(
+                        http::header::AUTHORIZATION,
+                        format!("Bearer {}", bearer_token(&config)),
+                    )
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], json!(404));
+        assert_eq!(body["message"], json!(format!("todo {missing_id} was not found")));
+    }
+
+    #[tokio::test]
+    async fn update_note() {
+        let todo = Todo {
+            id: Uuid::new_v4(),
+            user: None,
+            text: "todo 1".to_owned(),
+            completed: false,
+        };
+        let config = test_config();
+        let repository: Db = Arc::new(InMemoryRepository::new(HashMap::from([(todo.id, todo.clone())])));
+        let app = app(repository, config.clone());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::PUT)
+                    .uri(format!("/todos/{}", todo.id))
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .header(
+                        http::header::AUTHORIZATION,
+                        format!("Bearer {}", bearer_token(&config)),
+                    )
+                    .body(Body::from(
+                        serde_json::to_vec(&json!({"text": "updated", "user": Some("user")}))
+                            .unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: Todo = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.text, "updated".to_owned());
+        assert_eq!(body.user, Some("user".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn update_note_missing_id_returns_404() {
+        let config = test_config();
+        let app = app(Arc::new(InMemoryRepository::default()), config.clone());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::PUT)
+                    .uri(format!("/todos/{}", Uuid::new_v4()))
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .header(
+                        http::header::AUTHORIZATION,
+                        format!("Bearer {}", bearer_token(&config)),
+                    )
+                    .body(Body::from(
+                        serde_json::to_vec(&json!({"text": "updated", "user": Value::Null}))
+                            .unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn toggle_note() {
+        let todo = Todo {
+            id: Uuid::new_v4(),
+            user: None,
+            text: "todo 1".to_owned(),
+            completed: false,
+        };
+        let config = test_config();
+        let repository: Db = Arc::new(InMemoryRepository::new(HashMap::from([(todo.id, todo.clone())])));
+        let app = app(repository, config.clone());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::PATCH)
+                    .uri(format!("/todos/{}/done", todo.id))
+                    .header(
+                        http::header::AUTHORIZATION,
+                        format!("Bearer {}", bearer_token(&config)),
+                    )
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: Todo = serde_json::from_slice(&body).unwrap();
+        assert!(body.completed);
+    }
+
+    #[tokio::test]
+    async fn toggle_note_missing_id_returns_404() {
+        let config = test_config();
+        let app = app(Arc::new(InMemoryRepository::default()), config.clone());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::PATCH)
+                    .uri(format!("/todos/{}/done", Uuid::new_v4()))
+                    .header(
+                        http::header::AUTHORIZATION,
+                        format!("Bearer {}", bearer_token(&config)),
+                    )
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn delete_note() {
+        let todo = Todo {
+            id: Uuid::new_v4(),
+            user: None,
+            text: "todo 1".to_owned(),
+            completed: false,
+        };
+        let config = test_config();
+        let repository: Db = Arc::new(InMemoryRepository::new(HashMap::from([(todo.id, todo.clone())])));
+        let app = app(repository, config.clone());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::DELETE)
+                    .uri(format!("/todos/{}", todo.id))
+                    .header(
+                        http::header::AUTHORIZATION,
+                        format!("Bearer {}", bearer_token(&config)),
+                    )
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn delete_note_missing_id_returns_404() {
+        let config = test_config();
+        let app = app(Arc::new(InMemoryRepository::default()), config.clone());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::DELETE)
+                    .uri(format!("/todos/{}", Uuid::new_v4()))
+                    .header(
+                        http::header::AUTHORIZATION,
+                        format!("Bearer {}", bearer_token(&config)),
+                    )
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn search_todos_filters_by_text_completed_and_user() {
+        let matching = Todo {
+            id: Uuid::new_v4(),
+            user: Some("alice".to_owned()),
+            text: "buy milk".to_owned(),
+            completed: false,
+        };
+        let wrong_user = Todo {
+            id: Uuid::new_v4(),
+            user: Some("bob".to_owned()),
+            text: "buy milk".to_owned(),
+            completed: false,
+        };
+        let wrong_completed = Todo {
+            id: Uuid::new_v4(),
+            user: Some("alice".to_owned()),
+            text: "buy milk".to_owned(),
+            completed: true,
+        };
+        let wrong_text = Todo {
+            id: Uuid::new_v4(),
+            user: Some("alice".to_owned()),
+            text: "walk the dog".to_owned(),
+            completed: false,
+        };
+        let config = test_config();
+        let repository: Db = Arc::new(InMemoryRepository::new(HashMap::from([
+            (matching.id, matching.clone()),
+            (wrong_user.id, wrong_user),
+            (wrong_completed.id, wrong_completed),
+            (wrong_text.id, wrong_text),
+        ])));
+        let app = app(repository, config.clone());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/todos/search?text=milk&completed=false&user=alice")
+                    .header(
+                        http::header::AUTHORIZATION,
+                        format!("Bearer {}", bearer_token(&config)),
+                    )
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: Vec<Todo> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.len(), 1);
+        assert_eq!(body[0].id, matching.id);
+    }
+
     #[tokio::test]
     async fn empty_list() {
-        let app = app(Db::default());
+        let config = test_config();
+        let app = app(Arc::new(InMemoryRepository::default()), config.clone());
 
         let response = app
             .oneshot(
                 Request::builder()
                     .method(http::Method::GET)
                     .uri("/todos")
+                    .header(
+                        http::header::AUTHORIZATION,
+                        format!("Bearer {}", bearer_token(&config)),
+                    )
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -301,4 +908,148 @@ mod tests {
         assert!(body.as_array().is_some());
         assert!(body.as_array().unwrap().is_empty());
     }
+
+    #[tokio::test]
+    async fn rejects_missing_bearer_token() {
+        let app = app(Arc::new(InMemoryRepository::default()), test_config());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/todos")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn login_accepts_configured_admin_credentials() {
+        let config = test_config();
+        let app = app(Arc::new(InMemoryRepository::default()), config.clone());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/login")
+                    .header(http::header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(
+                        json!({
+                            "username": config.admin_username,
+                            "password": config.admin_password,
+                        })
+                        .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert!(body["token"].is_string());
+        assert_eq!(body["expires_in"], json!(config.jwt_expires_in));
+    }
+
+    #[tokio::test]
+    async fn login_rejects_wrong_credentials() {
+        let config = test_config();
+        let app = app(Arc::new(InMemoryRepository::default()), config);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/login")
+                    .header(http::header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(
+                        json!({
+                            "username": "admin",
+                            "password": "not-the-right-password",
+                        })
+                        .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn swagger_ui_serves_and_resolves_openapi_doc() {
+        let app = app(Arc::new(InMemoryRepository::default()), test_config());
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/swagger-ui/")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/api-doc/openapi.json")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn get_todos_as_csv() {
+        let todo = Todo {
+            id: Uuid::new_v4(),
+            user: Some("user".to_owned()),
+            text: "todo 1".to_owned(),
+            completed: false,
+        };
+        let config = test_config();
+        let repository: Db = Arc::new(InMemoryRepository::new(HashMap::from([(todo.id, todo)])));
+        let app = app(repository, config.clone());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/todos?format=csv")
+                    .header(
+                        http::header::AUTHORIZATION,
+                        format!("Bearer {}", bearer_token(&config)),
+                    )
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(http::header::CONTENT_TYPE).unwrap(),
+            "text/csv"
+        );
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("todo 1"));
+    }
 }
@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::{CreateTodo, SearchParams, Todo, UpdateTodo};
+
+/// Storage abstraction for todos, so handlers don't depend on a concrete map
+/// or database. Swap in a SQL- or RocksDB-backed implementation without
+/// touching any handler code. Methods return `Result` rather than bare
+/// `Option`/`Vec`/`bool` so a backend failure (e.g. a dropped SQL
+/// connection) can be told apart from a legitimate "not found"/"empty".
+#[async_trait]
+pub trait TodoRepository: Send + Sync {
+    async fn all(&self) -> Result<Vec<Todo>, ApiError>;
+    async fn find(&self, id: Uuid) -> Result<Option<Todo>, ApiError>;
+    async fn create(&self, input: CreateTodo) -> Result<Todo, ApiError>;
+    async fn update(&self, id: Uuid, input: UpdateTodo) -> Result<Option<Todo>, ApiError>;
+    async fn toggle(&self, id: Uuid) -> Result<Option<Todo>, ApiError>;
+    async fn delete(&self, id: Uuid) -> Result<bool, ApiError>;
+    async fn search(&self, params: SearchParams) -> Result<Vec<Todo>, ApiError>;
+}
+
+#[derive(Default)]
+pub struct InMemoryRepository {
+    todos: RwLock<HashMap<Uuid, Todo>>,
+}
+
+impl InMemoryRepository {
+    pub fn new(todos: HashMap<Uuid, Todo>) -> Self {
+        Self {
+            todos: RwLock::new(todos),
+        }
+    }
+}
+
+#[async_trait]
+impl TodoRepository for InMemoryRepository {
+    async fn all(&self) -> Result<Vec<Todo>, ApiError> {
+        Ok(self.todos.read().unwrap().values().cloned().collect())
+    }
+
+    async fn find(&self, id: Uuid) -> Result<Option<Todo>, ApiError> {
+        Ok(self.todos.read().unwrap().get(&id).cloned())
+    }
+
+    async fn create(&self, input: CreateTodo) -> Result<Todo, ApiError> {
+        let todo = Todo {
+            id: Uuid::new_v4(),
+            user: input.user,
+            text: input.text,
+            completed: false,
+        };
+
+        self.todos.write().unwrap().insert(todo.id, todo.clone());
+
+        Ok(todo)
+    }
+
+    async fn update(&self, id: Uuid, input: UpdateTodo) -> Result<Option<Todo>, ApiError> {
+        let mut todos = self.todos.write().unwrap();
+        let Some(todo) = todos.get_mut(&id) else {
+            return Ok(None);
+        };
+        todo.text = input.text;
+        todo.user = input.user;
+        Ok(Some(todo.clone()))
+    }
+
+    async fn toggle(&self, id: Uuid) -> Result<Option<Todo>, ApiError> {
+        let mut todos = self.todos.write().unwrap();
+        let Some(todo) = todos.get_mut(&id) else {
+            return Ok(None);
+        };
+        todo.completed = !todo.completed;
+        Ok(Some(todo.clone()))
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, ApiError> {
+        Ok(self.todos.write().unwrap().remove(&id).is_some())
+    }
+
+    async fn search(&self, params: SearchParams) -> Result<Vec<Todo>, ApiError> {
+        Ok(self
+            .todos
+            .read()
+            .unwrap()
+            .values()
+            .filter(|todo| {
+                params
+                    .text
+                    .as_ref()
+                    .map_or(true, |text| todo.text.contains(text.as_str()))
+            })
+            .filter(|todo| {
+                params
+                    .completed
+                    .map_or(true, |completed| todo.completed == completed)
+            })
+            .filter(|todo| {
+                params
+                    .user
+                    .as_ref()
+                    .map_or(true, |user| todo.user.as_deref() == Some(user.as_str()))
+            })
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::repository::TodoRepository;
+use crate::{CreateTodo, SearchParams, Todo, UpdateTodo};
+
+/// Postgres-backed `TodoRepository`. Selected at startup when `DATABASE_URL`
+/// is set, giving the example real durability across restarts while
+/// handlers stay untouched thanks to the trait boundary.
+pub struct SqlRepository {
+    pool: PgPool,
+}
+
+impl SqlRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_error(context: &'static str, error: sqlx::Error) -> ApiError {
+    tracing::error!(%error, context, "sql repository query failed");
+    ApiError::Internal
+}
+
+#[async_trait]
+impl TodoRepository for SqlRepository {
+    async fn all(&self) -> Result<Vec<Todo>, ApiError> {
+        sqlx::query_as::<_, Todo>(r#"SELECT id, text, "user", completed FROM todos"#)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|error| db_error("all", error))
+    }
+
+    async fn find(&self, id: Uuid) -> Result<Option<Todo>, ApiError> {
+        sqlx::query_as::<_, Todo>(
+            r#"SELECT id, text, "user", completed FROM todos WHERE id = $1"#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|error| db_error("find", error))
+    }
+
+    async fn create(&self, input: CreateTodo) -> Result<Todo, ApiError> {
+        let todo = Todo {
+            id: Uuid::new_v4(),
+            user: input.user,
+            text: input.text,
+            completed: false,
+        };
+
+        sqlx::query(r#"INSERT INTO todos (id, text, "user", completed) VALUES ($1, $2, $3, $4)"#)
+            .bind(todo.id)
+            .bind(&todo.text)
+            .bind(&todo.user)
+            .bind(todo.completed)
+            .execute(&self.pool)
+            .await
+            .map_err(|error| db_error("create", error))?;
+
+        Ok(todo)
+    }
+
+    async fn update(&self, id: Uuid, input: UpdateTodo) -> Result<Option<Todo>, ApiError> {
+        sqlx::query_as::<_, Todo>(
+            r#"UPDATE todos SET text = $2, "user" = $3 WHERE id = $1
+               RETURNING id, text, "user", completed"#,
+        )
+        .bind(id)
+        .bind(&input.text)
+        .bind(&input.user)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|error| db_error("update", error))
+    }
+
+    async fn toggle(&self, id: Uuid) -> Result<Option<Todo>, ApiError> {
+        sqlx::query_as::<_, Todo>(
+            r#"UPDATE todos SET completed = NOT completed WHERE id = $1
+               RETURNING id, text, "user", completed"#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|error| db_error("toggle", error))
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, ApiError> {
+        sqlx::query("DELETE FROM todos WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map(|result| result.rows_affected() > 0)
+            .map_err(|error| db_error("delete", error))
+    }
+
+    async fn search(&self, params: SearchParams) -> Result<Vec<Todo>, ApiError> {
+        sqlx::query_as::<_, Todo>(
+            r#"SELECT id, text, "user", completed FROM todos
+               WHERE ($1::text IS NULL OR text ILIKE '%' || $1 || '%')
+                 AND ($2::bool IS NULL OR completed = $2)
+                 AND ($3::text IS NULL OR "user" = $3)"#,
+        )
+        .bind(params.text)
+        .bind(params.completed)
+        .bind(params.user)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|error| db_error("search", error))
+    }
+}